@@ -0,0 +1,26 @@
+//! Internal alias for the atomic types used throughout this crate.
+//!
+//! Some embedded targets (certain single-core MCUs, AVR, RISC-V without the
+//! `A` extension, ...) lack the native compare-and-swap atomics these locks
+//! rely on. Enabling the `portable-atomic` feature swaps every atomic used
+//! here for its `portable_atomic` equivalent, which provides CAS through a
+//! critical section or a lock-free fallback on such targets. This module is
+//! the single cfg point for that switch.
+
+#[cfg(not(feature = "portable-atomic"))]
+pub(crate) use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(feature = "portable-atomic")]
+pub(crate) use portable_atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(test)]
+mod tests {
+    use crate::Mutex;
+
+    #[test]
+    fn mutex_works_through_the_atomic_alias() {
+        let lock: Mutex<i32> = Mutex::new(0);
+        *lock.lock() += 1;
+        assert_eq!(*lock.lock(), 1);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Mutex, RelaxStrategy, Spin};
+
+/// A barrier enables multiple threads to synchronize the beginning of some
+/// computation, blocking at `wait()` until a fixed number of threads have
+/// arrived.
+///
+/// Once released, the barrier resets itself and can be reused for another
+/// round of rendezvous.
+pub struct Barrier<R = Spin> {
+    lock: Mutex<BarrierState>,
+    num_threads: usize,
+    relax: core::marker::PhantomData<R>,
+}
+
+struct BarrierState {
+    count: usize,
+    generation: usize,
+}
+
+/// The result returned by [`Barrier::wait`], indicating whether this thread
+/// was the one that released the barrier.
+pub struct BarrierWaitResult(bool);
+
+impl<R> Barrier<R> {
+    /// Creates a new `Barrier` that will block `n` threads at `wait()` until
+    /// they have all arrived.
+    pub const fn new(n: usize) -> Barrier<R> {
+        Barrier {
+            lock: Mutex::new(BarrierState {
+                count: 0,
+                generation: 0,
+            }),
+            num_threads: n,
+            relax: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<R: RelaxStrategy> Barrier<R> {
+    /// Blocks the current thread until all `n` threads have reached this
+    /// point.
+    ///
+    /// The thread that arrives last resets the barrier and is returned as
+    /// the leader; every other thread spins until it observes that reset.
+    pub fn wait(&self) -> BarrierWaitResult {
+        let mut lock = self.lock.lock();
+        let local_generation = lock.generation;
+        lock.count += 1;
+
+        if lock.count < self.num_threads {
+            while local_generation == lock.generation {
+                drop(lock);
+                R::relax();
+                lock = self.lock.lock();
+            }
+            BarrierWaitResult(false)
+        } else {
+            lock.count = 0;
+            lock.generation = lock.generation.wrapping_add(1);
+            BarrierWaitResult(true)
+        }
+    }
+}
+
+impl BarrierWaitResult {
+    /// Returns `true` if this thread is the one that released the barrier.
+    pub fn is_leader(&self) -> bool {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Barrier;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn new_is_generic_over_relax_strategy() {
+        // Regression test: `Barrier::new` must stay generic over `R` so
+        // hosted callers can build a `Barrier<Yield>`, not just `Barrier`.
+        let _barrier: Barrier<crate::Yield> = Barrier::new(1);
+    }
+
+    #[test]
+    fn all_threads_rendezvous_with_exactly_one_leader() {
+        const N: usize = 8;
+
+        let barrier: Arc<Barrier> = Arc::new(Barrier::new(N));
+        let leaders = Arc::new(AtomicUsize::new(0));
+
+        let threads: std::vec::Vec<_> = (0..N)
+            .map(|_| {
+                let barrier = Arc::clone(&barrier);
+                let leaders = Arc::clone(&leaders);
+                thread::spawn(move || {
+                    if barrier.wait().is_leader() {
+                        leaders.fetch_add(1, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(leaders.load(Ordering::Relaxed), 1);
+    }
+}
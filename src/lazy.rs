@@ -0,0 +1,64 @@
+use core::ops::Deref;
+
+use crate::Once;
+
+/// A value which is initialized on the first access.
+///
+/// This is built on top of [`Once`] and is intended for lazily-initialized
+/// `static`s, e.g.
+///
+/// ```
+/// use spin::Lazy;
+///
+/// static CONFIG: Lazy<i32> = Lazy::new(|| 1 + 1);
+///
+/// assert_eq!(*CONFIG, 2);
+/// ```
+pub struct Lazy<T, F = fn() -> T> {
+    once: Once<T>,
+    init: F,
+}
+
+impl<T, F> Lazy<T, F> {
+    /// Creates a new `Lazy` that will be initialized with `f` on first access.
+    pub const fn new(f: F) -> Lazy<T, F> {
+        Lazy {
+            once: Once::new(),
+            init: f,
+        }
+    }
+}
+
+impl<T, F: Fn() -> T> Lazy<T, F> {
+    /// Forces the evaluation of this lazy value and returns a reference to
+    /// the result, initializing it if it hasn't been already.
+    pub fn force(this: &Lazy<T, F>) -> &T {
+        this.once.call_once(|| (this.init)())
+    }
+}
+
+impl<T, F: Fn() -> T> Deref for Lazy<T, F> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        Lazy::force(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lazy;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn force_runs_the_initializer_exactly_once() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let lazy = Lazy::new(|| {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            42
+        });
+
+        assert_eq!(*lazy, 42);
+        assert_eq!(*lazy, 42);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+}
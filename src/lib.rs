@@ -1,25 +1,30 @@
 #![crate_type = "lib"]
-#![feature(core)]
-#![feature(no_std, unsafe_destructor)]
 #![warn(missing_docs)]
+#![no_std]
 
 //! Synchronization primitives based on spinning
 
-#![no_std]
-
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
-#[macro_use]
-extern crate core;
-
+pub use barrier::*;
+pub use lazy::*;
 pub use mutex::*;
+pub use once::*;
+pub use relax::*;
 pub use rw_lock::*;
+pub use ticket_mutex::*;
 
+mod atomic;
+mod barrier;
+mod lazy;
 mod mutex;
+mod once;
+mod relax;
 mod rw_lock;
+mod ticket_mutex;
 
-#[cfg(not(test))]
-mod std {
-    pub use core::*;
-}
\ No newline at end of file
+/// Implementations of the [`lock_api`] traits for this crate's primitives,
+/// enabled by the `lock_api` feature.
+#[cfg(feature = "lock_api")]
+pub mod lock_api;
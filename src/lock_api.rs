@@ -0,0 +1,166 @@
+//! Implementations of the [`lock_api`] traits for this crate's primitives.
+//!
+//! Enabling the `lock_api` feature lets this crate interoperate with the
+//! wider `lock_api` ecosystem -- including `MappedMutexGuard` and
+//! `MappedRwLockReadGuard` support -- while the plain `Mutex`/`RwLock` types
+//! remain dependency-free when the feature is off.
+
+use crate::atomic::{AtomicBool, Ordering};
+use crate::{RelaxStrategy, Spin};
+
+/// A [`lock_api::RawMutex`] implementation using the same spinning algorithm
+/// as this crate's [`Mutex`](crate::Mutex), on an `AtomicBool` of its own.
+pub struct RawMutex<R = Spin> {
+    lock: AtomicBool,
+    relax: core::marker::PhantomData<R>,
+}
+
+unsafe impl<R: RelaxStrategy> lock_api::RawMutex for RawMutex<R> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = RawMutex {
+        lock: AtomicBool::new(false),
+        relax: core::marker::PhantomData,
+    };
+
+    type GuardMarker = lock_api::GuardSend;
+
+    fn lock(&self) {
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.lock.load(Ordering::Relaxed) {
+                R::relax();
+            }
+        }
+    }
+
+    fn try_lock(&self) -> bool {
+        self.lock
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    unsafe fn unlock(&self) {
+        self.lock.store(false, Ordering::Release);
+    }
+
+    fn is_locked(&self) -> bool {
+        self.lock.load(Ordering::Relaxed)
+    }
+}
+
+/// A [`lock_api::RawRwLock`] implementation backed by this crate's spinning
+/// [`RwLock`](crate::RwLock).
+pub struct RawRwLock<R = Spin> {
+    lock: crate::RwLock<(), R>,
+}
+
+unsafe impl<R: RelaxStrategy> lock_api::RawRwLock for RawRwLock<R> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = RawRwLock {
+        lock: crate::RwLock::with_relax(()),
+    };
+
+    type GuardMarker = lock_api::GuardSend;
+
+    fn lock_shared(&self) {
+        core::mem::forget(self.lock.read());
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        self.lock.try_read().map(core::mem::forget).is_some()
+    }
+
+    unsafe fn unlock_shared(&self) {
+        self.lock.force_unlock_read();
+    }
+
+    fn lock_exclusive(&self) {
+        core::mem::forget(self.lock.write());
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        self.lock.try_write().map(core::mem::forget).is_some()
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        self.lock.force_unlock_write();
+    }
+}
+
+unsafe impl<R: RelaxStrategy> lock_api::RawRwLockUpgrade for RawRwLock<R> {
+    fn lock_upgradable(&self) {
+        core::mem::forget(self.lock.upgradeable_read());
+    }
+
+    fn try_lock_upgradable(&self) -> bool {
+        self.lock
+            .try_upgradeable_read()
+            .map(core::mem::forget)
+            .is_some()
+    }
+
+    unsafe fn unlock_upgradable(&self) {
+        self.lock.force_unlock_upgradeable_read();
+    }
+
+    unsafe fn upgrade(&self) {
+        self.lock.force_upgrade();
+    }
+
+    unsafe fn try_upgrade(&self) -> bool {
+        self.lock.force_try_upgrade()
+    }
+}
+
+/// A [`Mutex`](crate::Mutex) usable through the [`lock_api`] guard API.
+pub type Mutex<T, R = Spin> = lock_api::Mutex<RawMutex<R>, T>;
+/// A [`MutexGuard`](crate::MutexGuard) usable through the [`lock_api`] guard API.
+pub type MutexGuard<'a, T, R = Spin> = lock_api::MutexGuard<'a, RawMutex<R>, T>;
+/// Type alias for a [`lock_api::MappedMutexGuard`] over [`RawMutex`].
+pub type MappedMutexGuard<'a, T, R = Spin> = lock_api::MappedMutexGuard<'a, RawMutex<R>, T>;
+
+/// An [`RwLock`](crate::RwLock) usable through the [`lock_api`] guard API.
+pub type RwLock<T, R = Spin> = lock_api::RwLock<RawRwLock<R>, T>;
+/// A read guard usable through the [`lock_api`] guard API.
+pub type RwLockReadGuard<'a, T, R = Spin> = lock_api::RwLockReadGuard<'a, RawRwLock<R>, T>;
+/// A write guard usable through the [`lock_api`] guard API.
+pub type RwLockWriteGuard<'a, T, R = Spin> = lock_api::RwLockWriteGuard<'a, RawRwLock<R>, T>;
+/// An upgradable read guard usable through the [`lock_api`] guard API.
+pub type RwLockUpgradableReadGuard<'a, T, R = Spin> =
+    lock_api::RwLockUpgradableReadGuard<'a, RawRwLock<R>, T>;
+/// Type alias for a [`lock_api::MappedRwLockReadGuard`] over [`RawRwLock`].
+pub type MappedRwLockReadGuard<'a, T, R = Spin> =
+    lock_api::MappedRwLockReadGuard<'a, RawRwLock<R>, T>;
+
+#[cfg(test)]
+mod tests {
+    use super::{Mutex, RwLock};
+
+    #[test]
+    fn mutex_mutual_exclusion() {
+        let lock: Mutex<i32> = Mutex::new(0);
+
+        let mut guard = lock.lock();
+        assert!(lock.try_lock().is_none());
+        *guard += 1;
+        drop(guard);
+
+        assert_eq!(*lock.lock(), 1);
+    }
+
+    #[test]
+    fn rwlock_upgrade() {
+        let lock: RwLock<i32> = RwLock::new(5);
+
+        let upgradeable = lock.upgradable_read();
+        assert!(lock.try_write().is_none());
+        let mut writer = lock_api::RwLockUpgradableReadGuard::upgrade(upgradeable);
+        *writer = 6;
+        drop(writer);
+
+        assert_eq!(*lock.read(), 6);
+    }
+}
@@ -0,0 +1,184 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+use crate::atomic::{AtomicBool, Ordering};
+use crate::{RelaxStrategy, Spin};
+
+/// A mutual exclusion primitive useful for protecting shared data
+///
+/// This mutex will spin threads trying to acquire the lock rather than
+/// putting them to sleep, which makes it suitable for use in `no_std`
+/// environments where no scheduler is available to park threads.
+///
+/// The spin loop's behaviour when contended is parameterized over a
+/// [`RelaxStrategy`] (defaulting to [`Spin`]); hosted users can opt into
+/// [`Yield`](crate::Yield) with the `std` feature instead.
+///
+/// # Example
+///
+/// ```
+/// use spin::Mutex;
+///
+/// let lock: Mutex<i32> = Mutex::new(0);
+///
+/// {
+///     let mut data = lock.lock();
+///     *data = 2;
+/// }
+///
+/// assert_eq!(*lock.lock(), 2);
+/// ```
+pub struct Mutex<T: ?Sized, R = Spin> {
+    lock: AtomicBool,
+    relax: PhantomData<R>,
+    data: UnsafeCell<T>,
+}
+
+/// A guard that provides mutable access to the data protected by a `Mutex`
+///
+/// When this structure is dropped (falls out of scope), the lock will be
+/// unlocked.
+pub struct MutexGuard<'a, T: ?Sized + 'a> {
+    lock: &'a AtomicBool,
+    data: &'a mut T,
+}
+
+unsafe impl<T: ?Sized + Send, R> Sync for Mutex<T, R> {}
+unsafe impl<T: ?Sized + Send, R> Send for Mutex<T, R> {}
+
+impl<T, R> Mutex<T, R> {
+    /// Creates a new `Mutex` wrapping the supplied data.
+    pub const fn new(data: T) -> Mutex<T, R> {
+        Mutex {
+            lock: AtomicBool::new(false),
+            relax: PhantomData,
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes this `Mutex` and unwraps the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized, R: RelaxStrategy> Mutex<T, R> {
+    fn obtain_lock(&self) {
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.lock.load(Ordering::Relaxed) {
+                R::relax();
+            }
+        }
+    }
+
+    /// Locks the `Mutex` and returns a guard that permits access to the inner data.
+    ///
+    /// The returned value may be dereferenced for data access and the lock
+    /// will be dropped when the guard falls out of scope.
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.obtain_lock();
+        MutexGuard {
+            lock: &self.lock,
+            data: unsafe { &mut *self.data.get() },
+        }
+    }
+}
+
+impl<T: ?Sized, R> Mutex<T, R> {
+    /// Tries to lock the `Mutex`, returning `None` if it is already locked.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        if self
+            .lock
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            Some(MutexGuard {
+                lock: &self.lock,
+                data: unsafe { &mut *self.data.get() },
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the lock is currently held.
+    pub fn is_locked(&self) -> bool {
+        self.lock.load(Ordering::Relaxed)
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `Mutex` mutably, no actual locking needs to
+    /// take place -- the mutable borrow statically guarantees no locks exist.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.data.get() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, R> fmt::Debug for Mutex<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.try_lock() {
+            Some(guard) => f.debug_struct("Mutex").field("data", &&*guard).finish(),
+            None => f.pad("Mutex { <locked> }"),
+        }
+    }
+}
+
+impl<T: Default, R> Default for Mutex<T, R> {
+    fn default() -> Mutex<T, R> {
+        Mutex {
+            lock: AtomicBool::new(false),
+            relax: PhantomData,
+            data: UnsafeCell::new(Default::default()),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for MutexGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for MutexGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.data
+    }
+}
+
+impl<'a, T: ?Sized> Drop for MutexGuard<'a, T> {
+    fn drop(&mut self) {
+        self.lock.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mutex, RelaxStrategy};
+
+    struct NoDefault(i32);
+
+    struct CustomRelax;
+
+    impl RelaxStrategy for CustomRelax {
+        fn relax() {
+            core::hint::spin_loop();
+        }
+    }
+
+    #[test]
+    fn new_is_generic_over_relax_strategy_for_non_default_t() {
+        // Regression test: `Mutex::new` must stay generic over `R` (and
+        // must not require `T: Default`) so callers can build e.g. a
+        // `Mutex<T, Yield>` for a `T` that doesn't implement `Default`.
+        let lock: Mutex<NoDefault, CustomRelax> = Mutex::new(NoDefault(1));
+        assert_eq!(lock.lock().0, 1);
+    }
+}
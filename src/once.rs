@@ -0,0 +1,146 @@
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+
+use crate::atomic::{AtomicUsize, Ordering};
+
+// The `Once` has not been initialized yet.
+const INCOMPLETE: usize = 0x0;
+// The `Once` is currently being initialized by some thread.
+const RUNNING: usize = 0x1;
+// The `Once` has fully initialized its value.
+const COMPLETE: usize = 0x2;
+// The initializer panicked while running, poisoning the `Once`.
+const PANICKED: usize = 0x3;
+
+/// A synchronization primitive which can be used to run a one-time global
+/// initialization, spinning to coordinate between racing threads rather than
+/// blocking on an OS primitive.
+///
+/// This is a `no_std`-friendly building block for lazily-initialized statics.
+pub struct Once<T> {
+    state: AtomicUsize,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send + Sync> Sync for Once<T> {}
+unsafe impl<T: Send> Send for Once<T> {}
+
+impl<T> Once<T> {
+    /// Creates a new `Once` that has not yet been initialized.
+    pub const fn new() -> Once<T> {
+        Once {
+            state: AtomicUsize::new(INCOMPLETE),
+            data: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Performs an initialization routine exactly once, even if called
+    /// concurrently from many threads.
+    ///
+    /// The first caller to reach `call_once` runs `f` and stores its result;
+    /// every other caller spins until that value is available and then
+    /// returns a reference to it.
+    pub fn call_once<F: FnOnce() -> T>(&self, f: F) -> &T {
+        if let Err(state) =
+            self.state
+                .compare_exchange(INCOMPLETE, RUNNING, Ordering::Acquire, Ordering::Acquire)
+        {
+            self.wait(state);
+        } else {
+            struct Guard<'a>(&'a AtomicUsize);
+            impl<'a> Drop for Guard<'a> {
+                fn drop(&mut self) {
+                    // If we're unwinding, `store` below never ran: mark the
+                    // `Once` as poisoned so later callers don't observe an
+                    // uninitialized value as complete.
+                    self.0.store(PANICKED, Ordering::SeqCst);
+                }
+            }
+
+            let guard = Guard(&self.state);
+            let value = f();
+            unsafe { (*self.data.get()).write(value) };
+            core::mem::forget(guard);
+            self.state.store(COMPLETE, Ordering::Release);
+        }
+
+        unsafe { self.force_get() }
+    }
+
+    fn wait(&self, mut state: usize) {
+        loop {
+            match state {
+                COMPLETE => return,
+                PANICKED => panic!("Once instance has previously been poisoned"),
+                _ => {
+                    core::hint::spin_loop();
+                    state = self.state.load(Ordering::Acquire);
+                }
+            }
+        }
+    }
+
+    /// Returns a reference to the inner value if it has been initialized.
+    pub fn get(&self) -> Option<&T> {
+        if self.is_completed() {
+            Some(unsafe { self.force_get() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the `Once` has been successfully initialized.
+    pub fn is_completed(&self) -> bool {
+        self.state.load(Ordering::Acquire) == COMPLETE
+    }
+
+    unsafe fn force_get(&self) -> &T {
+        &*(*self.data.get()).as_ptr()
+    }
+}
+
+impl<T> Default for Once<T> {
+    fn default() -> Once<T> {
+        Once::new()
+    }
+}
+
+impl<T> Drop for Once<T> {
+    fn drop(&mut self) {
+        if self.is_completed() {
+            unsafe {
+                core::ptr::drop_in_place((*self.data.get()).as_mut_ptr());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Once;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn call_once_runs_the_initializer_exactly_once() {
+        let once = Once::new();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..4 {
+            let value = once.call_once(|| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                42
+            });
+            assert_eq!(*value, 42);
+        }
+
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn get_is_none_until_initialized() {
+        let once: Once<u32> = Once::new();
+        assert_eq!(once.get(), None);
+        once.call_once(|| 7);
+        assert_eq!(once.get(), Some(&7));
+    }
+}
@@ -0,0 +1,82 @@
+/// A strategy for dealing with contention in a spin loop.
+///
+/// Implementors of this trait are called between failed attempts at
+/// acquiring a lock, which gives callers a chance to back off -- anything
+/// from simply hinting to the CPU that it is in a spin loop, up to yielding
+/// the current thread back to the OS scheduler.
+pub trait RelaxStrategy {
+    /// Relaxes the current thread or CPU for one iteration of a spin loop.
+    fn relax();
+}
+
+/// A strategy that hints the processor that it is running a busy loop, but
+/// otherwise does not yield control away from the current thread.
+///
+/// This is the default strategy, and the only one available to `no_std`
+/// users since it does not depend on a scheduler being present.
+pub struct Spin;
+
+impl RelaxStrategy for Spin {
+    #[inline(always)]
+    fn relax() {
+        core::hint::spin_loop();
+    }
+}
+
+/// A strategy that yields the current time slice to the scheduler in favour
+/// of other threads whenever a lock is found to be contended.
+///
+/// This is preferable to [`Spin`] on hosted platforms where the lock holder
+/// may have been descheduled, since busy-waiting for it wastes cycles that
+/// could otherwise go to the thread that will release the lock.
+#[cfg(feature = "std")]
+pub struct Yield;
+
+#[cfg(feature = "std")]
+impl RelaxStrategy for Yield {
+    #[inline(always)]
+    fn relax() {
+        std::thread::yield_now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    struct CountingRelax;
+
+    static RELAX_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    impl super::RelaxStrategy for CountingRelax {
+        fn relax() {
+            RELAX_CALLS.fetch_add(1, Ordering::Relaxed);
+            core::hint::spin_loop();
+        }
+    }
+
+    #[test]
+    fn mutex_is_generic_over_relax_strategy() {
+        let lock: Arc<Mutex<i32, CountingRelax>> = Arc::new(Mutex::new(0));
+
+        // Hold the lock on this thread while another thread contends for
+        // it, so the contender is forced through `CountingRelax::relax()`
+        // at least once before it can proceed.
+        let guard = lock.lock();
+        let contender = thread::spawn({
+            let lock = Arc::clone(&lock);
+            move || *lock.lock() += 1
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(RELAX_CALLS.load(Ordering::Relaxed) > 0);
+
+        drop(guard);
+        contender.join().unwrap();
+        assert_eq!(*lock.lock(), 1);
+    }
+}
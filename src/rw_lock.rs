@@ -0,0 +1,410 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+use crate::atomic::{AtomicUsize, Ordering};
+use crate::{RelaxStrategy, Spin};
+
+const WRITER: usize = 1;
+const UPGRADED: usize = 1 << 1;
+const READER: usize = 1 << 2;
+
+/// A lock that provides data access to either one writer or many readers.
+///
+/// This lock behaves in a similar manner to its namesake `std::sync::RwLock`
+/// but uses spinning to synchronize instead of blocking on a futex. The
+/// spin loop's behaviour when contended is parameterized over a
+/// [`RelaxStrategy`] (defaulting to [`Spin`]).
+pub struct RwLock<T: ?Sized, R = Spin> {
+    lock: AtomicUsize,
+    relax: PhantomData<R>,
+    data: UnsafeCell<T>,
+}
+
+/// A guard that provides immutable data access.
+pub struct RwLockReadGuard<'a, T: ?Sized + 'a> {
+    lock: &'a AtomicUsize,
+    data: &'a T,
+}
+
+/// A guard that provides mutable data access.
+pub struct RwLockWriteGuard<'a, T: ?Sized + 'a, R = Spin> {
+    lock: &'a AtomicUsize,
+    data: &'a mut T,
+    relax: PhantomData<R>,
+}
+
+/// A guard that grants shared read access, but which can be upgraded to a
+/// [`RwLockWriteGuard`].
+///
+/// At most one `RwLockUpgradeableGuard` can exist at a time, but it may
+/// coexist with any number of plain [`RwLockReadGuard`]s.
+pub struct RwLockUpgradeableGuard<'a, T: ?Sized + 'a, R = Spin> {
+    lock: &'a AtomicUsize,
+    data: *const T,
+    relax: PhantomData<R>,
+}
+
+unsafe impl<T: ?Sized + Send, R> Send for RwLock<T, R> {}
+unsafe impl<T: ?Sized + Send + Sync, R> Sync for RwLock<T, R> {}
+
+unsafe impl<T: ?Sized + Send, R> Send for RwLockUpgradeableGuard<'_, T, R> {}
+unsafe impl<T: ?Sized + Sync, R> Sync for RwLockUpgradeableGuard<'_, T, R> {}
+
+impl<T, R> RwLock<T, R> {
+    /// Creates a new `RwLock` wrapping the supplied data.
+    pub const fn new(data: T) -> RwLock<T, R> {
+        RwLock {
+            lock: AtomicUsize::new(0),
+            relax: PhantomData,
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Creates a new `RwLock` wrapping the supplied data; identical to
+    /// [`RwLock::new`], which already accepts any relax strategy.
+    #[cfg(feature = "lock_api")]
+    pub(crate) const fn with_relax(data: T) -> RwLock<T, R> {
+        RwLock {
+            lock: AtomicUsize::new(0),
+            data: UnsafeCell::new(data),
+            relax: PhantomData,
+        }
+    }
+
+    /// Consumes this `RwLock` and unwraps the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized, R: RelaxStrategy> RwLock<T, R> {
+    /// Locks this `RwLock` with shared read access, blocking the current
+    /// thread until it can be acquired.
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        loop {
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+            R::relax();
+        }
+    }
+
+    /// Locks this `RwLock` with exclusive write access, blocking the current
+    /// thread until it can be acquired.
+    pub fn write(&self) -> RwLockWriteGuard<'_, T, R> {
+        loop {
+            if let Some(guard) = self.try_write() {
+                return guard;
+            }
+            R::relax();
+        }
+    }
+
+    /// Locks this `RwLock` with upgradeable read access, blocking the current
+    /// thread until it can be acquired.
+    ///
+    /// At most one upgradeable read lock may be held at a time, though
+    /// ordinary readers may still access the data concurrently with it.
+    pub fn upgradeable_read(&self) -> RwLockUpgradeableGuard<'_, T, R> {
+        loop {
+            if let Some(guard) = self.try_upgradeable_read() {
+                return guard;
+            }
+            R::relax();
+        }
+    }
+}
+
+impl<T: ?Sized, R> RwLock<T, R> {
+    /// Attempts to lock this `RwLock` with shared read access.
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        let value = self.lock.fetch_add(READER, Ordering::Acquire);
+        if value & WRITER != 0 {
+            self.lock.fetch_sub(READER, Ordering::Release);
+            None
+        } else {
+            Some(RwLockReadGuard {
+                lock: &self.lock,
+                data: unsafe { &*self.data.get() },
+            })
+        }
+    }
+
+    /// Attempts to lock this `RwLock` with exclusive write access.
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T, R>> {
+        if self
+            .lock
+            .compare_exchange(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            Some(RwLockWriteGuard {
+                lock: &self.lock,
+                data: unsafe { &mut *self.data.get() },
+                relax: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to lock this `RwLock` with upgradeable read access.
+    ///
+    /// Fails if another upgradeable or write guard is already held, even if
+    /// that guard does not conflict with ordinary readers.
+    pub fn try_upgradeable_read(&self) -> Option<RwLockUpgradeableGuard<'_, T, R>> {
+        let value = self.lock.fetch_or(UPGRADED, Ordering::Acquire);
+        if value & (WRITER | UPGRADED) == 0 {
+            Some(RwLockUpgradeableGuard {
+                lock: &self.lock,
+                data: self.data.get(),
+                relax: PhantomData,
+            })
+        } else {
+            if value & UPGRADED == 0 {
+                // We were the one who just set UPGRADED, but a writer beat us -- undo it.
+                self.lock.fetch_and(!UPGRADED, Ordering::Release);
+            }
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.data.get() }
+    }
+}
+
+#[cfg(feature = "lock_api")]
+impl<T: ?Sized, R> RwLock<T, R> {
+    /// Releases a read lock taken out of band, e.g. via a forgotten guard.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure a read lock is actually held.
+    pub(crate) unsafe fn force_unlock_read(&self) {
+        self.lock.fetch_sub(READER, Ordering::Release);
+    }
+
+    /// Releases a write lock taken out of band, e.g. via a forgotten guard.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure the write lock is actually held.
+    pub(crate) unsafe fn force_unlock_write(&self) {
+        self.lock.fetch_and(!WRITER, Ordering::Release);
+    }
+
+    /// Releases an upgradeable read lock taken out of band, e.g. via a
+    /// forgotten guard.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure the upgradeable read lock is actually held.
+    pub(crate) unsafe fn force_unlock_upgradeable_read(&self) {
+        self.lock.fetch_and(!UPGRADED, Ordering::Release);
+    }
+
+    /// Upgrades an upgradeable read lock taken out of band to a write lock,
+    /// blocking until all readers have drained.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure the upgradeable read lock is actually held.
+    pub(crate) unsafe fn force_upgrade(&self)
+    where
+        R: RelaxStrategy,
+    {
+        while self
+            .lock
+            .compare_exchange(UPGRADED, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            R::relax();
+        }
+    }
+
+    /// Tries to upgrade an upgradeable read lock taken out of band to a
+    /// write lock without blocking.
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure the upgradeable read lock is actually held.
+    pub(crate) unsafe fn force_try_upgrade(&self) -> bool {
+        self.lock
+            .compare_exchange(UPGRADED, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, R> fmt::Debug for RwLock<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.try_read() {
+            Some(guard) => f.debug_struct("RwLock").field("data", &&*guard).finish(),
+            None => f.pad("RwLock { <locked> }"),
+        }
+    }
+}
+
+impl<T: Default, R> Default for RwLock<T, R> {
+    fn default() -> RwLock<T, R> {
+        RwLock {
+            lock: AtomicUsize::new(0),
+            relax: PhantomData,
+            data: UnsafeCell::new(Default::default()),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for RwLockReadGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<'a, T: ?Sized> Drop for RwLockReadGuard<'a, T> {
+    fn drop(&mut self) {
+        self.lock.fetch_sub(READER, Ordering::Release);
+    }
+}
+
+impl<'a, T: ?Sized, R> Deref for RwLockWriteGuard<'a, T, R> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<'a, T: ?Sized, R> DerefMut for RwLockWriteGuard<'a, T, R> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.data
+    }
+}
+
+impl<'a, T: ?Sized, R> Drop for RwLockWriteGuard<'a, T, R> {
+    fn drop(&mut self) {
+        self.lock.fetch_and(!WRITER, Ordering::Release);
+    }
+}
+
+impl<'a, T: ?Sized, R> Deref for RwLockUpgradeableGuard<'a, T, R> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.data }
+    }
+}
+
+impl<'a, T: ?Sized, R> Drop for RwLockUpgradeableGuard<'a, T, R> {
+    fn drop(&mut self) {
+        self.lock.fetch_and(!UPGRADED, Ordering::Release);
+    }
+}
+
+impl<'a, T: ?Sized, R: RelaxStrategy> RwLockUpgradeableGuard<'a, T, R> {
+    /// Upgrades this guard to a [`RwLockWriteGuard`], blocking until all
+    /// concurrent readers have drained.
+    pub fn upgrade(mut self) -> RwLockWriteGuard<'a, T, R> {
+        loop {
+            match self.try_upgrade_internal() {
+                Ok(guard) => return guard,
+                Err(guard) => self = guard,
+            }
+            R::relax();
+        }
+    }
+}
+
+impl<'a, T: ?Sized, R> RwLockUpgradeableGuard<'a, T, R> {
+    /// Tries to upgrade this guard to a [`RwLockWriteGuard`] without
+    /// blocking, returning the original guard back if there are still
+    /// readers other than this one.
+    pub fn try_upgrade(self) -> Result<RwLockWriteGuard<'a, T, R>, Self> {
+        self.try_upgrade_internal()
+    }
+
+    fn try_upgrade_internal(self) -> Result<RwLockWriteGuard<'a, T, R>, Self> {
+        match self
+            .lock
+            .compare_exchange(UPGRADED, WRITER, Ordering::Acquire, Ordering::Relaxed)
+        {
+            Ok(_) => {
+                let lock = self.lock;
+                let data = self.data as *mut T;
+                core::mem::forget(self);
+                Ok(RwLockWriteGuard {
+                    lock,
+                    data: unsafe { &mut *data },
+                    relax: PhantomData,
+                })
+            }
+            Err(_) => Err(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RwLock;
+    use crate::RelaxStrategy;
+
+    struct NoDefault(i32);
+
+    struct CustomRelax;
+
+    impl RelaxStrategy for CustomRelax {
+        fn relax() {
+            core::hint::spin_loop();
+        }
+    }
+
+    #[test]
+    fn new_is_generic_over_relax_strategy_for_non_default_t() {
+        // Regression test: `RwLock::new` must stay generic over `R` (and
+        // must not require `T: Default`) so callers can build e.g. an
+        // `RwLock<T, Yield>` for a `T` that doesn't implement `Default`.
+        let lock: RwLock<NoDefault, CustomRelax> = RwLock::new(NoDefault(1));
+        assert_eq!(lock.read().0, 1);
+    }
+
+    #[test]
+    fn readers_share_writers_exclude() {
+        let lock: RwLock<_> = RwLock::new(5);
+
+        let r1 = lock.read();
+        let r2 = lock.read();
+        assert_eq!((*r1, *r2), (5, 5));
+        assert!(lock.try_write().is_none());
+        drop((r1, r2));
+
+        let mut w = lock.write();
+        assert!(lock.try_read().is_none());
+        *w = 6;
+        drop(w);
+
+        assert_eq!(*lock.read(), 6);
+    }
+
+    #[test]
+    fn upgrade_after_readers_drain() {
+        let lock: RwLock<_> = RwLock::new(5);
+
+        let upgradeable = lock.upgradeable_read();
+        let reader = lock.read();
+        assert_eq!(*upgradeable, 5);
+
+        // A reader is still outstanding, so the upgrade can't complete yet.
+        let upgradeable = match upgradeable.try_upgrade() {
+            Ok(_) => panic!("upgrade should not succeed while a reader is held"),
+            Err(upgradeable) => upgradeable,
+        };
+        drop(reader);
+
+        let mut writer = upgradeable.upgrade();
+        *writer = 6;
+        drop(writer);
+
+        assert_eq!(*lock.read(), 6);
+    }
+}
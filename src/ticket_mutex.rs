@@ -0,0 +1,162 @@
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use crate::atomic::{AtomicUsize, Ordering};
+
+/// A FIFO mutual exclusion primitive useful for protecting shared data.
+///
+/// Unlike [`Mutex`](crate::Mutex), which gives no ordering guarantees,
+/// `TicketMutex` hands out the lock in the order threads arrived, so no
+/// thread can starve under heavy contention. This comes at the cost of a
+/// little extra bookkeeping compared to the plain unfair mutex.
+///
+/// # Example
+///
+/// ```
+/// use spin::TicketMutex;
+///
+/// let lock = TicketMutex::new(0);
+///
+/// {
+///     let mut data = lock.lock();
+///     *data = 2;
+/// }
+///
+/// assert_eq!(*lock.lock(), 2);
+/// ```
+pub struct TicketMutex<T: ?Sized> {
+    next_ticket: AtomicUsize,
+    now_serving: AtomicUsize,
+    data: UnsafeCell<T>,
+}
+
+/// A guard that provides mutable access to the data protected by a `TicketMutex`.
+pub struct TicketMutexGuard<'a, T: ?Sized + 'a> {
+    now_serving: &'a AtomicUsize,
+    data: &'a mut T,
+}
+
+unsafe impl<T: ?Sized + Send> Sync for TicketMutex<T> {}
+unsafe impl<T: ?Sized + Send> Send for TicketMutex<T> {}
+
+impl<T> TicketMutex<T> {
+    /// Creates a new `TicketMutex` wrapping the supplied data.
+    pub const fn new(data: T) -> TicketMutex<T> {
+        TicketMutex {
+            next_ticket: AtomicUsize::new(0),
+            now_serving: AtomicUsize::new(0),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consumes this `TicketMutex` and unwraps the underlying data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> TicketMutex<T> {
+    /// Locks the `TicketMutex` and returns a guard that permits access to the inner data.
+    ///
+    /// Guards are handed out in the order their tickets were claimed, so
+    /// threads are served in the order they called `lock()`.
+    pub fn lock(&self) -> TicketMutexGuard<'_, T> {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+
+        while self.now_serving.load(Ordering::Acquire) != ticket {
+            core::hint::spin_loop();
+        }
+
+        TicketMutexGuard {
+            now_serving: &self.now_serving,
+            data: unsafe { &mut *self.data.get() },
+        }
+    }
+
+    /// Tries to lock the `TicketMutex`, returning `None` if another thread is
+    /// already waiting for or holding the lock.
+    pub fn try_lock(&self) -> Option<TicketMutexGuard<'_, T>> {
+        self.next_ticket
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |next| {
+                if next == self.now_serving.load(Ordering::Acquire) {
+                    Some(next + 1)
+                } else {
+                    None
+                }
+            })
+            .ok()?;
+
+        Some(TicketMutexGuard {
+            now_serving: &self.now_serving,
+            data: unsafe { &mut *self.data.get() },
+        })
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.data.get() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for TicketMutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.try_lock() {
+            Some(guard) => f.debug_struct("TicketMutex").field("data", &&*guard).finish(),
+            None => f.pad("TicketMutex { <locked> }"),
+        }
+    }
+}
+
+impl<T: Default> Default for TicketMutex<T> {
+    fn default() -> TicketMutex<T> {
+        TicketMutex::new(Default::default())
+    }
+}
+
+impl<'a, T: ?Sized> Deref for TicketMutexGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for TicketMutexGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.data
+    }
+}
+
+impl<'a, T: ?Sized> Drop for TicketMutexGuard<'a, T> {
+    fn drop(&mut self) {
+        self.now_serving.fetch_add(1, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TicketMutex;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn mutual_exclusion() {
+        let lock = Arc::new(TicketMutex::new(0));
+        let threads: std::vec::Vec<_> = (0..8)
+            .map(|_| {
+                let lock = Arc::clone(&lock);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        *lock.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+
+        for t in threads {
+            t.join().unwrap();
+        }
+
+        assert_eq!(*lock.lock(), 8000);
+    }
+}